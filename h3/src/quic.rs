@@ -69,6 +69,30 @@ impl Error for SendDatagramError {
     }
 }
 
+/// Snapshot of path and congestion-control state for a [`Connection`].
+///
+/// Backends fill in whatever they can observe; fields a backend cannot provide should be left
+/// at their default (zero/`None`) rather than guessed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConnectionStats {
+    /// Smoothed round-trip time estimate
+    pub smoothed_rtt: std::time::Duration,
+    /// The most recently sampled round-trip time
+    pub latest_rtt: std::time::Duration,
+    /// Congestion window, in bytes
+    pub congestion_window: u64,
+    /// Bytes sent but not yet acknowledged or declared lost
+    pub bytes_in_flight: u64,
+    /// Total packets sent on this connection
+    pub packets_sent: u64,
+    /// Total packets declared lost on this connection
+    pub packets_lost: u64,
+    /// Current path MTU, in bytes
+    pub path_mtu: u16,
+    /// Current maximum size of an outgoing QUIC datagram, in bytes
+    pub max_udp_payload_size: u64,
+}
+
 /// Trait representing a QUIC connection.
 pub trait Connection {
     /// The type produced by `poll_accept_bidi()`
@@ -83,8 +107,10 @@ pub trait Connection {
         RecvStream = Self::RecvStream,
         BidiStream = Self::BidiStream,
     >;
-    /// Error type yielded by this trait methods
-    type Error: Into<Box<dyn Error>>;
+    /// Error type yielded by `poll_accept_recv`/`poll_accept_bidi`/`poll_accept_datagram`
+    type AcceptError: Into<Box<dyn Error>>;
+    /// Error type yielded by `poll_open_bidi`/`poll_open_send`
+    type OpenError: Into<Box<dyn Error>>;
 
     /// Accept an incoming unidirectional stream
     ///
@@ -92,7 +118,7 @@ pub trait Connection {
     fn poll_accept_recv(
         &mut self,
         cx: &mut task::Context<'_>,
-    ) -> Poll<Result<Option<Self::RecvStream>, Self::Error>>;
+    ) -> Poll<Result<Option<Self::RecvStream>, Self::AcceptError>>;
 
     /// Accept an incoming bidirectional stream
     ///
@@ -100,19 +126,19 @@ pub trait Connection {
     fn poll_accept_bidi(
         &mut self,
         cx: &mut task::Context<'_>,
-    ) -> Poll<Result<Option<Self::BidiStream>, Self::Error>>;
+    ) -> Poll<Result<Option<Self::BidiStream>, Self::AcceptError>>;
 
     /// Poll the connection to create a new bidirectional stream.
     fn poll_open_bidi(
         &mut self,
         cx: &mut task::Context<'_>,
-    ) -> Poll<Result<Self::BidiStream, Self::Error>>;
+    ) -> Poll<Result<Self::BidiStream, Self::OpenError>>;
 
     /// Poll the connection to create a new unidirectional stream.
     fn poll_open_send(
         &mut self,
         cx: &mut task::Context<'_>,
-    ) -> Poll<Result<Self::SendStream, Self::Error>>;
+    ) -> Poll<Result<Self::SendStream, Self::OpenError>>;
 
     /// Get an object to open outgoing streams.
     fn opener(&self) -> Self::OpenStreams;
@@ -120,17 +146,50 @@ pub trait Connection {
     /// Close the connection immediately
     fn close(&mut self, code: crate::error::Code, reason: &[u8]);
 
+    /// Poll whether 0-RTT (early) data sent on this connection was accepted by the peer.
+    ///
+    /// A `Connection` value can exist, and be used to open streams through [`opener`
+    /// ][Self::opener], before the handshake has confirmed if it was constructed from 0-RTT
+    /// (early) data — see the backend's connection-establishment API (e.g. quinn's
+    /// `Connecting::into_0rtt`) for how to obtain one; that construction step happens before a
+    /// `Connection` exists and so is outside this trait's surface. This method is how a caller
+    /// who did so learns, after the fact, whether the peer actually accepted that early data:
+    /// resolves once the handshake confirms, `true` meaning the peer processed any streams or
+    /// requests opened before confirmation, `false` meaning it rejected them and the connection
+    /// fell back to a full handshake, so the caller must re-send anything non-idempotent that
+    /// was sent as early data. Connections that never attempted 0-RTT, or backends that don't
+    /// support it, resolve immediately to `false`.
+    fn poll_zero_rtt_accepted(&mut self, cx: &mut task::Context<'_>) -> Poll<bool>;
+
     /// Poll the connection for incoming datagrams.
     fn poll_accept_datagram(
         &mut self,
         cx: &mut task::Context<'_>,
-    ) -> Poll<Result<Option<Bytes>, Self::Error>>;
+    ) -> Poll<Result<Option<Bytes>, Self::AcceptError>>;
 
     /// Send a datagram
     fn send_datagram(&mut self, data: Bytes) -> Result<(), SendDatagramError>;
+
+    /// The current maximum size of a datagram that [`send_datagram`][Self::send_datagram] will
+    /// accept, or `None` if datagrams are unsupported or unnegotiated.
+    ///
+    /// This tracks the negotiated path MTU and so may change over the connection's lifetime;
+    /// re-read it before each send (or batch of sends) rather than caching it.
+    fn max_datagram_size(&self) -> Option<usize>;
+
+    /// Get the current [`ConnectionStats`] for this connection.
+    ///
+    /// Unlike the `poll_*` methods, this is a plain accessor: backends that track this state
+    /// synchronously (such as quinn) can return it without going through the executor.
+    fn stats(&self) -> ConnectionStats;
 }
 
 /// Trait for opening outgoing streams
+///
+/// If the owning [`Connection`] was constructed from 0-RTT (early) data, streams opened through
+/// here before the handshake confirms are sent as early data too; see
+/// [`Connection::poll_zero_rtt_accepted`] for how the caller learns whether the peer accepted
+/// them.
 pub trait OpenStreams {
     /// The type produced by `poll_open_bidi()`
     type BidiStream: SendStream + RecvStream;
@@ -139,19 +198,19 @@ pub trait OpenStreams {
     /// The type of the receiving part of `BidiStream`
     type RecvStream: RecvStream;
     /// Error type yielded by these trait methods
-    type Error: Into<Box<dyn Error>>;
+    type OpenError: Into<Box<dyn Error>>;
 
     /// Poll the connection to create a new bidirectional stream.
     fn poll_open_bidi(
         &mut self,
         cx: &mut task::Context<'_>,
-    ) -> Poll<Result<Self::BidiStream, Self::Error>>;
+    ) -> Poll<Result<Self::BidiStream, Self::OpenError>>;
 
     /// Poll the connection to create a new unidirectional stream.
     fn poll_open_uni(
         &mut self,
         cx: &mut task::Context<'_>,
-    ) -> Poll<Result<Self::SendStream, Self::Error>>;
+    ) -> Poll<Result<Self::SendStream, Self::OpenError>>;
 
     /// Close the connection immediately
     fn close(&mut self, code: crate::error::Code, reason: &[u8]);
@@ -218,3 +277,334 @@ pub trait BidiStream: SendStream + RecvStream {
     /// Split this stream into two halves.
     fn split(self) -> (Self::SendStream, Self::RecvStream);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{noop_waker, NOT_EXERCISED};
+
+    #[derive(Debug)]
+    struct MockError;
+
+    impl fmt::Display for MockError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "mock error")
+        }
+    }
+
+    impl std::error::Error for MockError {}
+
+    impl Error for MockError {
+        fn is_timeout(&self) -> bool {
+            false
+        }
+
+        fn err_code(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    /// A stream that's never actually driven; this mock only exists to satisfy the
+    /// [`Connection::OpenStreams`]/stream associated types so [`MockConnection`] is a complete
+    /// implementor, letting the test below reach `stats()`/`max_datagram_size()`/
+    /// `poll_zero_rtt_accepted()` through the same trait the harness programs against.
+    #[derive(Debug)]
+    struct MockStream;
+
+    impl SendStream for MockStream {
+        type Error = MockError;
+
+        fn poll_send<D: Buf>(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+            _buf: &mut D,
+        ) -> Poll<Result<usize, Self::Error>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_finish(&mut self, _cx: &mut task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn reset(&mut self, _reset_code: u64) {}
+
+        fn send_id(&self) -> StreamId {
+            StreamId::try_from(0u64).unwrap()
+        }
+    }
+
+    impl RecvStream for MockStream {
+        type Buf = Bytes;
+        type Error = MockError;
+
+        fn poll_data(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn stop_sending(&mut self, _error_code: u64) {}
+
+        fn recv_id(&self) -> StreamId {
+            StreamId::try_from(0u64).unwrap()
+        }
+    }
+
+    struct MockOpener;
+
+    impl OpenStreams for MockOpener {
+        type BidiStream = MockStream;
+        type SendStream = MockStream;
+        type RecvStream = MockStream;
+        type OpenError = MockError;
+
+        fn poll_open_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::BidiStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_open_uni(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::SendStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn close(&mut self, _code: crate::error::Code, _reason: &[u8]) {}
+    }
+
+    /// A [`Connection`] whose accessor methods return fixed values, standing in for a real
+    /// backend so the trait's non-stream surface can be exercised without one.
+    struct MockConnection {
+        stats: ConnectionStats,
+        max_datagram_size: Option<usize>,
+        zero_rtt_accepted: bool,
+    }
+
+    impl Connection for MockConnection {
+        type BidiStream = MockStream;
+        type SendStream = MockStream;
+        type RecvStream = MockStream;
+        type OpenStreams = MockOpener;
+        type AcceptError = MockError;
+        type OpenError = MockError;
+
+        fn poll_accept_recv(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::RecvStream>, Self::AcceptError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_accept_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::BidiStream>, Self::AcceptError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_open_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::BidiStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_open_send(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::SendStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn opener(&self) -> Self::OpenStreams {
+            MockOpener
+        }
+
+        fn close(&mut self, _code: crate::error::Code, _reason: &[u8]) {}
+
+        fn poll_zero_rtt_accepted(&mut self, _cx: &mut task::Context<'_>) -> Poll<bool> {
+            Poll::Ready(self.zero_rtt_accepted)
+        }
+
+        fn poll_accept_datagram(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Bytes>, Self::AcceptError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn send_datagram(&mut self, _data: Bytes) -> Result<(), SendDatagramError> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn max_datagram_size(&self) -> Option<usize> {
+            self.max_datagram_size
+        }
+
+        fn stats(&self) -> ConnectionStats {
+            self.stats
+        }
+    }
+
+    #[test]
+    fn stats_max_datagram_size_and_zero_rtt_accepted_are_reachable_through_the_trait() {
+        let mut conn = MockConnection {
+            stats: ConnectionStats {
+                congestion_window: 4096,
+                ..Default::default()
+            },
+            max_datagram_size: Some(1200),
+            zero_rtt_accepted: true,
+        };
+
+        // Exercised through a generic bound rather than the concrete type, matching how the
+        // harness only ever holds a `Connection` impl, never `MockConnection` itself.
+        fn check<C: Connection>(conn: &mut C) {
+            assert_eq!(conn.stats().congestion_window, 4096);
+            assert_eq!(conn.max_datagram_size(), Some(1200));
+
+            let waker = noop_waker();
+            let mut cx = task::Context::from_waker(&waker);
+            assert_eq!(conn.poll_zero_rtt_accepted(&mut cx), Poll::Ready(true));
+        }
+
+        check(&mut conn);
+    }
+
+    /// The accept side's failure mode: the peer never showed up in time. Distinct from
+    /// [`MockOpenReset`] to prove `AcceptError`/`OpenError` can genuinely be different types,
+    /// not just different names for the same one.
+    #[derive(Debug)]
+    struct MockAcceptTimeout;
+
+    impl fmt::Display for MockAcceptTimeout {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "accept timed out")
+        }
+    }
+
+    impl std::error::Error for MockAcceptTimeout {}
+
+    impl Error for MockAcceptTimeout {
+        fn is_timeout(&self) -> bool {
+            true
+        }
+
+        fn err_code(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    /// The open side's failure mode: the peer reset the stream with an application error code.
+    /// Carries data ([`Self::0`]) that [`MockAcceptTimeout`] has no use for, which a shared
+    /// error type for both sides couldn't express.
+    #[derive(Debug)]
+    struct MockOpenReset(u64);
+
+    impl fmt::Display for MockOpenReset {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "stream reset with code {}", self.0)
+        }
+    }
+
+    impl std::error::Error for MockOpenReset {}
+
+    impl Error for MockOpenReset {
+        fn is_timeout(&self) -> bool {
+            false
+        }
+
+        fn err_code(&self) -> Option<u64> {
+            Some(self.0)
+        }
+    }
+
+    struct MockSplitErrorConnection;
+
+    impl Connection for MockSplitErrorConnection {
+        type BidiStream = MockStream;
+        type SendStream = MockStream;
+        type RecvStream = MockStream;
+        type OpenStreams = MockOpener;
+        type AcceptError = MockAcceptTimeout;
+        type OpenError = MockOpenReset;
+
+        fn poll_accept_recv(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::RecvStream>, Self::AcceptError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_accept_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::BidiStream>, Self::AcceptError>> {
+            Poll::Ready(Err(MockAcceptTimeout))
+        }
+
+        fn poll_open_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::BidiStream, Self::OpenError>> {
+            Poll::Ready(Err(MockOpenReset(42)))
+        }
+
+        fn poll_open_send(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::SendStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn opener(&self) -> Self::OpenStreams {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn close(&mut self, _code: crate::error::Code, _reason: &[u8]) {}
+
+        fn poll_zero_rtt_accepted(&mut self, _cx: &mut task::Context<'_>) -> Poll<bool> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_accept_datagram(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Bytes>, Self::AcceptError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn send_datagram(&mut self, _data: Bytes) -> Result<(), SendDatagramError> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn max_datagram_size(&self) -> Option<usize> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn stats(&self) -> ConnectionStats {
+            unimplemented!(NOT_EXERCISED)
+        }
+    }
+
+    #[test]
+    fn accept_error_and_open_error_can_be_genuinely_distinct_types() {
+        let waker = noop_waker();
+        let mut cx = task::Context::from_waker(&waker);
+        let mut conn = MockSplitErrorConnection;
+
+        match conn.poll_accept_bidi(&mut cx) {
+            Poll::Ready(Err(e)) => assert!(e.is_timeout()),
+            other => panic!("expected poll_accept_bidi to report a timeout, got {other:?}"),
+        }
+
+        match conn.poll_open_bidi(&mut cx) {
+            Poll::Ready(Err(e)) => assert_eq!(e.err_code(), Some(42)),
+            other => panic!("expected poll_open_bidi to report a reset code, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Test-only doubles shared by this crate's `#[cfg(test)]` modules, so each one isn't
+//! re-inventing the same mock boilerplate.
+
+#![cfg(test)]
+
+use std::task::{RawWaker, RawWakerVTable, Waker};
+
+/// A [`Waker`] that does nothing when woken. For polling mock types that are driven to
+/// completion in a single poll and never actually register interest in being woken again.
+pub(crate) fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn no_op(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, no_op, no_op, no_op);
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+/// What a mock method that exists only to satisfy a trait's associated types panics with, if a
+/// test ever calls it unexpectedly.
+pub(crate) const NOT_EXERCISED: &str = "not exercised by this test";
@@ -0,0 +1,983 @@
+//! WebTransport over HTTP/3
+//!
+//! This module implements the WebTransport extension ([draft-ietf-webtrans-http3]) on top of
+//! the generic QUIC transport traits in [`crate::quic`]. A [`Session`] multiplexes application
+//! streams and datagrams over a single extended-CONNECT request, demultiplexing incoming
+//! streams/datagrams that belong to it from the ones belonging to the rest of the HTTP/3
+//! connection.
+//!
+//! This module owns the WebTransport-specific framing ([`Session`]'s stream/datagram
+//! signal+session-ID prefixes) and the pure negotiation decisions
+//! ([`WebTransportSettings::from_raw`]/[`is_negotiated`]/[`is_extended_connect`]). It does not
+//! own decoding the control stream's SETTINGS frame or dispatching incoming requests by their
+//! pseudo-headers — that lives in the connection driver and request-dispatch code, which calls
+//! into the helpers here once it has decoded the raw settings/headers, and constructs a
+//! [`Session`] from the resulting extended-CONNECT request.
+//!
+//! [draft-ietf-webtrans-http3]: https://www.ietf.org/archive/id/draft-ietf-webtrans-http3-07.html
+
+use core::fmt;
+use std::io::Cursor;
+use std::task::{self, Poll};
+
+use bytes::{Buf, Bytes};
+
+use crate::proto::stream::StreamId;
+use crate::proto::varint::VarInt;
+use crate::quic::{self, RecvStream, SendDatagramError, SendStream};
+
+/// The `SETTINGS_ENABLE_WEBTRANSPORT` HTTP/3 SETTINGS parameter.
+///
+/// Sent by both endpoints during the control-stream handshake to negotiate support for
+/// WebTransport sessions before any extended CONNECT request is made.
+pub const SETTINGS_ENABLE_WEBTRANSPORT: u64 = 0x2b603742;
+
+/// The `H3_DATAGRAM` HTTP/3 SETTINGS parameter.
+///
+/// WebTransport requires HTTP/3 datagram support, so this must be negotiated alongside
+/// [`SETTINGS_ENABLE_WEBTRANSPORT`].
+pub const H3_DATAGRAM: u64 = 0x33;
+
+/// The `:protocol` pseudo-header value identifying a WebTransport session request on an
+/// extended-CONNECT stream (RFC 8441 extended CONNECT, as used by
+/// [draft-ietf-webtrans-http3]).
+pub const WEBTRANSPORT_PROTOCOL: &str = "webtransport";
+
+/// A peer's relevant HTTP/3 SETTINGS values, decoded off the control stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WebTransportSettings {
+    /// Whether [`SETTINGS_ENABLE_WEBTRANSPORT`] was sent with a non-zero value.
+    pub enable_webtransport: bool,
+    /// Whether [`H3_DATAGRAM`] was sent with a non-zero value.
+    pub h3_datagram: bool,
+}
+
+impl WebTransportSettings {
+    /// Read the two settings this extension needs out of a SETTINGS frame's raw `(id, value)`
+    /// pairs.
+    ///
+    /// A setting is enabled when its value is non-zero; per the HTTP/3 SETTINGS rules, a setting
+    /// absent from `settings` is treated the same as one sent with value `0` (disabled).
+    pub fn from_raw<'a>(settings: impl IntoIterator<Item = &'a (u64, u64)>) -> Self {
+        let mut this = Self::default();
+        for &(id, value) in settings {
+            match id {
+                SETTINGS_ENABLE_WEBTRANSPORT => this.enable_webtransport = value != 0,
+                H3_DATAGRAM => this.h3_datagram = value != 0,
+                _ => {}
+            }
+        }
+        this
+    }
+}
+
+/// Whether WebTransport can be used on a connection, given both endpoints' settings.
+///
+/// WebTransport requires HTTP/3 datagrams, so [`SETTINGS_ENABLE_WEBTRANSPORT`] and
+/// [`H3_DATAGRAM`] must both be enabled, by both endpoints.
+pub fn is_negotiated(local: WebTransportSettings, peer: WebTransportSettings) -> bool {
+    local.enable_webtransport && local.h3_datagram && peer.enable_webtransport && peer.h3_datagram
+}
+
+/// Whether a request is a WebTransport extended-CONNECT, given its decoded `:method` and
+/// `:protocol` pseudo-headers.
+pub fn is_extended_connect(method: &str, protocol: Option<&str>) -> bool {
+    method.eq_ignore_ascii_case("CONNECT") && protocol == Some(WEBTRANSPORT_PROTOCOL)
+}
+
+/// Stream signal value prefixing a bidirectional stream opened for a WebTransport session.
+///
+/// Sent as a varint at the start of the stream, followed by the session ID.
+pub const WEBTRANSPORT_STREAM: u64 = 0x41;
+
+/// Stream signal value prefixing a unidirectional stream opened for a WebTransport session.
+///
+/// Sent as a varint stream type, followed by the session ID.
+pub const WEBTRANSPORT_UNI_STREAM: u64 = 0x54;
+
+/// Identifies a WebTransport session within an HTTP/3 connection.
+///
+/// This is the stream ID of the CONNECT request that established the session, re-used as a
+/// varint when tagging streams and datagrams that belong to it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SessionId(StreamId);
+
+impl SessionId {
+    /// Build a session ID from the stream ID of the extended-CONNECT request.
+    pub(crate) fn new(id: StreamId) -> Self {
+        Self(id)
+    }
+
+    /// The quarter-stream-ID used to prefix datagrams belonging to this session.
+    ///
+    /// Per the datagram extension, the session's stream ID is divided by four before being
+    /// encoded, since it is always a client-initiated bidirectional stream.
+    pub fn datagram_id(&self) -> u64 {
+        self.0.into_inner() / 4
+    }
+
+    fn raw(&self) -> u64 {
+        self.0.into_inner()
+    }
+}
+
+impl From<SessionId> for StreamId {
+    fn from(id: SessionId) -> Self {
+        id.0
+    }
+}
+
+/// Builds the header (signal varint + session-ID varint) that must precede application data on
+/// a stream opened for a WebTransport session.
+fn stream_header(signal: u64, id: SessionId) -> Cursor<Vec<u8>> {
+    let mut buf = Vec::with_capacity(16);
+    VarInt::from(signal).encode(&mut buf).expect("signal varint");
+    VarInt::from(id.raw())
+        .encode(&mut buf)
+        .expect("session id varint");
+    Cursor::new(buf)
+}
+
+/// Tries to parse a stream header (signal varint + session-ID varint) out of the front of
+/// `buf`. Returns `None` if `buf` doesn't yet hold a complete header; the caller should read
+/// more data and retry.
+fn try_parse_header(buf: &[u8]) -> Option<(u64, u64, usize)> {
+    let mut cursor = buf;
+    let start = cursor.remaining();
+    let signal = VarInt::decode(&mut cursor).ok()?;
+    let session = VarInt::decode(&mut cursor).ok()?;
+    let consumed = start - cursor.remaining();
+    Some((signal.into_inner(), session.into_inner(), consumed))
+}
+
+/// Error produced while reading the stream header of an incoming WebTransport stream.
+#[derive(Debug)]
+enum HeaderError<E> {
+    /// The underlying stream returned an error before the header finished arriving.
+    Stream(E),
+    /// The stream's sender closed it before a full header arrived.
+    Truncated,
+}
+
+impl<E: fmt::Display> fmt::Display for HeaderError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stream(e) => write!(f, "{e}"),
+            Self::Truncated => write!(f, "stream closed before WebTransport header was complete"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for HeaderError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Stream(e) => Some(e),
+            Self::Truncated => None,
+        }
+    }
+}
+
+impl<E: quic::Error> quic::Error for HeaderError<E> {
+    fn is_timeout(&self) -> bool {
+        matches!(self, Self::Stream(e) if e.is_timeout())
+    }
+
+    fn err_code(&self) -> Option<u64> {
+        match self {
+            Self::Stream(e) => e.err_code(),
+            Self::Truncated => None,
+        }
+    }
+}
+
+/// Reads from `stream` until `buf` holds a complete header, then returns the parsed
+/// `(signal, session id, bytes of buf consumed by the header)`.
+fn poll_read_header<S: RecvStream>(
+    stream: &mut S,
+    buf: &mut Vec<u8>,
+    cx: &mut task::Context<'_>,
+) -> Poll<Result<(u64, u64, usize), HeaderError<S::Error>>> {
+    loop {
+        if let Some(parsed) = try_parse_header(buf) {
+            return Poll::Ready(Ok(parsed));
+        }
+        match stream.poll_data(cx) {
+            Poll::Ready(Ok(Some(mut chunk))) => {
+                // `chunk.chunk()` only ever exposes the first contiguous segment of a
+                // multi-segment `Buf`; loop so a non-`Bytes` `RecvStream::Buf` (from a backend
+                // other than the one the included tests mock) doesn't get silently truncated.
+                while chunk.has_remaining() {
+                    buf.extend_from_slice(chunk.chunk());
+                    let len = chunk.chunk().len();
+                    chunk.advance(len);
+                }
+            }
+            Poll::Ready(Ok(None)) => return Poll::Ready(Err(HeaderError::Truncated)),
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(HeaderError::Stream(e))),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+}
+
+/// Writes `header` to `stream`, driving `poll_send` until it is fully flushed.
+fn poll_write_header<S: SendStream>(
+    stream: &mut S,
+    header: &mut Cursor<Vec<u8>>,
+    cx: &mut task::Context<'_>,
+) -> Poll<Result<(), S::Error>> {
+    while header.has_remaining() {
+        match stream.poll_send(cx, header) {
+            Poll::Ready(Ok(0)) => return Poll::Pending,
+            Poll::Ready(Ok(_)) => continue,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// A chunk read off a [`StrippedStream`]: either data already buffered while looking for the
+/// stream header, or a chunk read straight from the inner stream.
+enum StrippedBuf<B> {
+    Leftover(Bytes),
+    Inner(B),
+}
+
+impl<B: Buf> Buf for StrippedBuf<B> {
+    fn remaining(&self) -> usize {
+        match self {
+            Self::Leftover(b) => b.remaining(),
+            Self::Inner(b) => b.remaining(),
+        }
+    }
+
+    fn chunk(&self) -> &[u8] {
+        match self {
+            Self::Leftover(b) => b.chunk(),
+            Self::Inner(b) => b.chunk(),
+        }
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        match self {
+            Self::Leftover(b) => b.advance(cnt),
+            Self::Inner(b) => b.advance(cnt),
+        }
+    }
+}
+
+/// A stream accepted for a WebTransport session, with its `WEBTRANSPORT_STREAM`/unidirectional
+/// stream-type header already consumed from the front of its receive side.
+pub struct StrippedStream<S> {
+    inner: S,
+    leftover: Option<Bytes>,
+}
+
+impl<S: SendStream> SendStream for StrippedStream<S> {
+    type Error = S::Error;
+
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, Self::Error>> {
+        self.inner.poll_send(cx, buf)
+    }
+
+    fn poll_finish(&mut self, cx: &mut task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_finish(cx)
+    }
+
+    fn reset(&mut self, reset_code: u64) {
+        self.inner.reset(reset_code)
+    }
+
+    fn send_id(&self) -> StreamId {
+        self.inner.send_id()
+    }
+}
+
+impl<S: RecvStream> RecvStream for StrippedStream<S> {
+    type Buf = StrippedBuf<S::Buf>;
+    type Error = S::Error;
+
+    fn poll_data(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+        if let Some(leftover) = self.leftover.take() {
+            return Poll::Ready(Ok(Some(StrippedBuf::Leftover(leftover))));
+        }
+        match self.inner.poll_data(cx) {
+            Poll::Ready(Ok(chunk)) => Poll::Ready(Ok(chunk.map(StrippedBuf::Inner))),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn stop_sending(&mut self, error_code: u64) {
+        self.inner.stop_sending(error_code)
+    }
+
+    fn recv_id(&self) -> StreamId {
+        self.inner.recv_id()
+    }
+}
+
+enum OpenState<S> {
+    Idle,
+    Writing { stream: S, header: Cursor<Vec<u8>> },
+}
+
+enum AcceptState<S> {
+    Idle,
+    Parsing { stream: S, buf: Vec<u8> },
+}
+
+/// An established WebTransport session.
+///
+/// Wraps the underlying HTTP/3 [`quic::Connection`] so application streams and datagrams can be
+/// opened, accepted, and demultiplexed without the caller having to know about the WebTransport
+/// stream/datagram framing.
+///
+/// `Session` only tracks traffic for its own session ID: streams and datagrams that carry a
+/// different session's ID are not returned, and there is no registry here of sibling sessions to
+/// hand them off to, so they are reset/discarded rather than silently corrupting this session's
+/// stream ordering.
+pub struct Session<C>
+where
+    C: quic::Connection,
+{
+    conn: C,
+    id: SessionId,
+    open_bi: OpenState<C::BidiStream>,
+    open_uni: OpenState<C::SendStream>,
+    accept_bi: AcceptState<C::BidiStream>,
+    accept_uni: AcceptState<C::RecvStream>,
+}
+
+impl<C> Session<C>
+where
+    C: quic::Connection,
+{
+    /// Wrap a connection as a WebTransport session that has already completed its
+    /// extended-CONNECT handshake.
+    pub(crate) fn new(conn: C, id: SessionId) -> Self {
+        Self {
+            conn,
+            id,
+            open_bi: OpenState::Idle,
+            open_uni: OpenState::Idle,
+            accept_bi: AcceptState::Idle,
+            accept_uni: AcceptState::Idle,
+        }
+    }
+
+    /// The ID of this session.
+    pub fn id(&self) -> SessionId {
+        self.id
+    }
+
+    /// Poll the connection to create a new bidirectional stream for this session.
+    ///
+    /// The `WEBTRANSPORT_STREAM` signal and session ID are written to the stream before it is
+    /// handed back; the caller only needs to write application data.
+    pub fn poll_open_bi(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<StrippedStream<C::BidiStream>, Box<dyn quic::Error>>> {
+        loop {
+            match &mut self.open_bi {
+                OpenState::Idle => match self.conn.poll_open_bidi(cx) {
+                    Poll::Ready(Ok(stream)) => {
+                        self.open_bi = OpenState::Writing {
+                            stream,
+                            header: stream_header(WEBTRANSPORT_STREAM, self.id),
+                        };
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                    Poll::Pending => return Poll::Pending,
+                },
+                OpenState::Writing { stream, header } => {
+                    match poll_write_header(stream, header, cx) {
+                        Poll::Ready(Ok(())) => {
+                            let stream = match std::mem::replace(&mut self.open_bi, OpenState::Idle)
+                            {
+                                OpenState::Writing { stream, .. } => stream,
+                                OpenState::Idle => unreachable!(),
+                            };
+                            return Poll::Ready(Ok(StrippedStream {
+                                inner: stream,
+                                leftover: None,
+                            }));
+                        }
+                        Poll::Ready(Err(e)) => {
+                            self.open_bi = OpenState::Idle;
+                            return Poll::Ready(Err(e.into()));
+                        }
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Poll the connection to create a new unidirectional stream for this session.
+    ///
+    /// The unidirectional stream type and session ID are written to the stream before it is
+    /// handed back.
+    pub fn poll_open_uni(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<StrippedStream<C::SendStream>, Box<dyn quic::Error>>> {
+        loop {
+            match &mut self.open_uni {
+                OpenState::Idle => match self.conn.poll_open_send(cx) {
+                    Poll::Ready(Ok(stream)) => {
+                        self.open_uni = OpenState::Writing {
+                            stream,
+                            header: stream_header(WEBTRANSPORT_UNI_STREAM, self.id),
+                        };
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                    Poll::Pending => return Poll::Pending,
+                },
+                OpenState::Writing { stream, header } => {
+                    match poll_write_header(stream, header, cx) {
+                        Poll::Ready(Ok(())) => {
+                            let stream =
+                                match std::mem::replace(&mut self.open_uni, OpenState::Idle) {
+                                    OpenState::Writing { stream, .. } => stream,
+                                    OpenState::Idle => unreachable!(),
+                                };
+                            return Poll::Ready(Ok(StrippedStream {
+                                inner: stream,
+                                leftover: None,
+                            }));
+                        }
+                        Poll::Ready(Err(e)) => {
+                            self.open_uni = OpenState::Idle;
+                            return Poll::Ready(Err(e.into()));
+                        }
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Accept an incoming bidirectional stream that was opened for this session.
+    ///
+    /// Streams whose header names a different session are reset rather than returned; the
+    /// caller only ever sees streams addressed to [`id`][Self::id].
+    pub fn poll_accept_bi(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<StrippedStream<C::BidiStream>>, Box<dyn quic::Error>>> {
+        loop {
+            if matches!(self.accept_bi, AcceptState::Idle) {
+                match self.conn.poll_accept_bidi(cx) {
+                    Poll::Ready(Ok(Some(stream))) => {
+                        self.accept_bi = AcceptState::Parsing {
+                            stream,
+                            buf: Vec::with_capacity(16),
+                        };
+                    }
+                    Poll::Ready(Ok(None)) => return Poll::Ready(Ok(None)),
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            let (stream, buf) = match &mut self.accept_bi {
+                AcceptState::Parsing { stream, buf } => (stream, buf),
+                AcceptState::Idle => unreachable!(),
+            };
+
+            match poll_read_header(stream, buf, cx) {
+                Poll::Ready(Ok((signal, session, consumed))) => {
+                    let (mut stream, buf) =
+                        match std::mem::replace(&mut self.accept_bi, AcceptState::Idle) {
+                            AcceptState::Parsing { stream, buf } => (stream, buf),
+                            AcceptState::Idle => unreachable!(),
+                        };
+                    let leftover = buf[consumed..].to_vec();
+                    if signal != WEBTRANSPORT_STREAM || session != self.id.raw() {
+                        // Not addressed to this session: we have no registry of sibling
+                        // sessions to route it to, so close it out and keep looking.
+                        stream.reset(0);
+                        continue;
+                    }
+                    return Poll::Ready(Ok(Some(StrippedStream {
+                        inner: stream,
+                        leftover: (!leftover.is_empty()).then(|| Bytes::from(leftover)),
+                    })));
+                }
+                Poll::Ready(Err(e)) => {
+                    self.accept_bi = AcceptState::Idle;
+                    return Poll::Ready(Err(e.into()));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    /// Accept an incoming unidirectional stream that was opened for this session.
+    ///
+    /// Streams whose header names a different session are stopped rather than returned; the
+    /// caller only ever sees streams addressed to [`id`][Self::id].
+    pub fn poll_accept_uni(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<StrippedStream<C::RecvStream>>, Box<dyn quic::Error>>> {
+        loop {
+            if matches!(self.accept_uni, AcceptState::Idle) {
+                match self.conn.poll_accept_recv(cx) {
+                    Poll::Ready(Ok(Some(stream))) => {
+                        self.accept_uni = AcceptState::Parsing {
+                            stream,
+                            buf: Vec::with_capacity(16),
+                        };
+                    }
+                    Poll::Ready(Ok(None)) => return Poll::Ready(Ok(None)),
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            let (stream, buf) = match &mut self.accept_uni {
+                AcceptState::Parsing { stream, buf } => (stream, buf),
+                AcceptState::Idle => unreachable!(),
+            };
+
+            match poll_read_header(stream, buf, cx) {
+                Poll::Ready(Ok((signal, session, consumed))) => {
+                    let (mut stream, buf) =
+                        match std::mem::replace(&mut self.accept_uni, AcceptState::Idle) {
+                            AcceptState::Parsing { stream, buf } => (stream, buf),
+                            AcceptState::Idle => unreachable!(),
+                        };
+                    let leftover = buf[consumed..].to_vec();
+                    if signal != WEBTRANSPORT_UNI_STREAM || session != self.id.raw() {
+                        stream.stop_sending(0);
+                        continue;
+                    }
+                    return Poll::Ready(Ok(Some(StrippedStream {
+                        inner: stream,
+                        leftover: (!leftover.is_empty()).then(|| Bytes::from(leftover)),
+                    })));
+                }
+                Poll::Ready(Err(e)) => {
+                    self.accept_uni = AcceptState::Idle;
+                    return Poll::Ready(Err(e.into()));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    /// Poll for an incoming datagram addressed to this session.
+    ///
+    /// Datagrams whose quarter-stream-ID prefix does not match [`SessionId::datagram_id`] belong
+    /// to a different session and are dropped rather than surfaced here.
+    pub fn poll_read_datagram(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Bytes>, <C as quic::Connection>::AcceptError>> {
+        loop {
+            match self.conn.poll_accept_datagram(cx) {
+                Poll::Ready(Ok(Some(mut datagram))) => match VarInt::decode(&mut datagram) {
+                    Ok(id) if id.into_inner() == self.id.datagram_id() => {
+                        return Poll::Ready(Ok(Some(datagram)))
+                    }
+                    _ => continue,
+                },
+                Poll::Ready(Ok(None)) => return Poll::Ready(Ok(None)),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    /// Send a datagram on this session.
+    ///
+    /// The session's quarter-stream-ID is prefixed to `data` before handing it to the
+    /// underlying connection.
+    pub fn send_datagram(&mut self, data: Bytes) -> Result<(), SendDatagramError> {
+        let mut framed = Vec::with_capacity(8 + data.len());
+        VarInt::from(self.id.datagram_id())
+            .encode(&mut framed)
+            .expect("datagram id varint");
+        framed.extend_from_slice(&data);
+        self.conn.send_datagram(Bytes::from(framed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{noop_waker, NOT_EXERCISED};
+
+    #[test]
+    fn stream_header_round_trips_through_try_parse_header() {
+        let id = SessionId::new(StreamId::try_from(4u64).unwrap());
+        let mut header = stream_header(WEBTRANSPORT_STREAM, id);
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(header.chunk());
+        header.advance(header.remaining());
+
+        let (signal, session, consumed) = try_parse_header(&bytes).expect("complete header");
+        assert_eq!(signal, WEBTRANSPORT_STREAM);
+        assert_eq!(session, id.raw());
+        assert_eq!(consumed, bytes.len());
+    }
+
+    #[test]
+    fn try_parse_header_reports_incomplete_input() {
+        assert_eq!(try_parse_header(&[]), None);
+    }
+
+    #[test]
+    fn datagram_id_is_session_stream_id_divided_by_four() {
+        let id = SessionId::new(StreamId::try_from(8u64).unwrap());
+        assert_eq!(id.datagram_id(), 2);
+    }
+
+    #[test]
+    fn web_transport_settings_from_raw_ignores_zero_values_and_unknown_ids() {
+        let settings = WebTransportSettings::from_raw(&[
+            (SETTINGS_ENABLE_WEBTRANSPORT, 1),
+            (H3_DATAGRAM, 0),
+            (0x1234, 1),
+        ]);
+        assert!(settings.enable_webtransport);
+        assert!(!settings.h3_datagram);
+    }
+
+    #[test]
+    fn is_negotiated_requires_both_settings_on_both_sides() {
+        let both = WebTransportSettings {
+            enable_webtransport: true,
+            h3_datagram: true,
+        };
+        let datagram_only = WebTransportSettings {
+            enable_webtransport: false,
+            h3_datagram: true,
+        };
+
+        assert!(is_negotiated(both, both));
+        assert!(!is_negotiated(both, datagram_only));
+        assert!(!is_negotiated(datagram_only, both));
+        assert!(!is_negotiated(WebTransportSettings::default(), both));
+    }
+
+    #[test]
+    fn is_extended_connect_matches_connect_with_webtransport_protocol() {
+        assert!(is_extended_connect("CONNECT", Some("webtransport")));
+        assert!(is_extended_connect("connect", Some("webtransport")));
+        assert!(!is_extended_connect("GET", Some("webtransport")));
+        assert!(!is_extended_connect("CONNECT", Some("websocket")));
+        assert!(!is_extended_connect("CONNECT", None));
+    }
+
+    #[derive(Debug)]
+    struct MockStreamError;
+
+    impl fmt::Display for MockStreamError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "mock stream error")
+        }
+    }
+
+    impl std::error::Error for MockStreamError {}
+
+    impl quic::Error for MockStreamError {
+        fn is_timeout(&self) -> bool {
+            false
+        }
+
+        fn err_code(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    /// A bidirectional stream backed by a shared in-memory buffer, so a test can open one end
+    /// with one [`MockConnection`] and accept the other with a second instance sharing the same
+    /// [`Network`].
+    struct MockBidiStream(std::rc::Rc<std::cell::RefCell<std::collections::VecDeque<u8>>>);
+
+    impl SendStream for MockBidiStream {
+        type Error = MockStreamError;
+
+        fn poll_send<D: Buf>(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+            buf: &mut D,
+        ) -> Poll<Result<usize, Self::Error>> {
+            let n = buf.remaining();
+            self.0.borrow_mut().extend(buf.chunk());
+            buf.advance(n);
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_finish(&mut self, _cx: &mut task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn reset(&mut self, _reset_code: u64) {}
+
+        fn send_id(&self) -> StreamId {
+            StreamId::try_from(0u64).unwrap()
+        }
+    }
+
+    impl RecvStream for MockBidiStream {
+        type Buf = Bytes;
+        type Error = MockStreamError;
+
+        fn poll_data(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+            let drained: Vec<u8> = self.0.borrow_mut().drain(..).collect();
+            if drained.is_empty() {
+                return Poll::Ready(Ok(None));
+            }
+            Poll::Ready(Ok(Some(Bytes::from(drained))))
+        }
+
+        fn stop_sending(&mut self, _error_code: u64) {}
+
+        fn recv_id(&self) -> StreamId {
+            StreamId::try_from(0u64).unwrap()
+        }
+    }
+
+    struct MockOpener;
+
+    impl quic::OpenStreams for MockOpener {
+        type BidiStream = MockBidiStream;
+        type SendStream = MockBidiStream;
+        type RecvStream = MockBidiStream;
+        type OpenError = MockStreamError;
+
+        fn poll_open_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::BidiStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_open_uni(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::SendStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn close(&mut self, _code: crate::error::Code, _reason: &[u8]) {}
+    }
+
+    /// The one pending bidirectional stream a [`MockConnection::poll_open_bidi`] call hands off
+    /// for a peer [`MockConnection`] to pick up via `poll_accept_bidi`.
+    #[derive(Default)]
+    struct Network {
+        pending: std::cell::RefCell<Option<MockBidiStream>>,
+    }
+
+    #[derive(Clone)]
+    struct MockConnection(std::rc::Rc<Network>);
+
+    impl quic::Connection for MockConnection {
+        type BidiStream = MockBidiStream;
+        type SendStream = MockBidiStream;
+        type RecvStream = MockBidiStream;
+        type OpenStreams = MockOpener;
+        type AcceptError = MockStreamError;
+        type OpenError = MockStreamError;
+
+        fn poll_accept_recv(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::RecvStream>, Self::AcceptError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_accept_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::BidiStream>, Self::AcceptError>> {
+            Poll::Ready(Ok(self.0.pending.borrow_mut().take()))
+        }
+
+        fn poll_open_bidi(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::BidiStream, Self::OpenError>> {
+            let buf = std::rc::Rc::new(std::cell::RefCell::new(std::collections::VecDeque::new()));
+            *self.0.pending.borrow_mut() = Some(MockBidiStream(buf.clone()));
+            Poll::Ready(Ok(MockBidiStream(buf)))
+        }
+
+        fn poll_open_send(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Self::SendStream, Self::OpenError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn opener(&self) -> Self::OpenStreams {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn close(&mut self, _code: crate::error::Code, _reason: &[u8]) {}
+
+        fn poll_zero_rtt_accepted(&mut self, _cx: &mut task::Context<'_>) -> Poll<bool> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn poll_accept_datagram(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Bytes>, Self::AcceptError>> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn send_datagram(&mut self, _data: Bytes) -> Result<(), SendDatagramError> {
+            unimplemented!(NOT_EXERCISED)
+        }
+
+        fn max_datagram_size(&self) -> Option<usize> {
+            None
+        }
+
+        fn stats(&self) -> quic::ConnectionStats {
+            Default::default()
+        }
+    }
+
+    #[test]
+    fn session_open_bi_round_trips_the_header_through_accept_bi() {
+        let waker = noop_waker();
+        let mut cx = task::Context::from_waker(&waker);
+
+        let network = std::rc::Rc::new(Network::default());
+        let id = SessionId::new(StreamId::try_from(4u64).unwrap());
+
+        let mut opener = Session::new(MockConnection(network.clone()), id);
+        let mut acceptor = Session::new(MockConnection(network), id);
+
+        let mut opened = match opener.poll_open_bi(&mut cx) {
+            Poll::Ready(Ok(stream)) => stream,
+            Poll::Ready(Err(_)) => panic!("expected poll_open_bi to succeed"),
+            Poll::Pending => panic!("expected poll_open_bi to finish synchronously"),
+        };
+
+        // Application data written right after the header should come back out of
+        // `accept_bi`'s stream as leftover, not be mistaken for more header.
+        let mut payload = Cursor::new(b"hello".to_vec());
+        match opened.poll_send(&mut cx, &mut payload) {
+            Poll::Ready(Ok(5)) => {}
+            other => panic!("expected the payload to send synchronously, got {other:?}"),
+        }
+
+        let mut accepted = match acceptor.poll_accept_bi(&mut cx) {
+            Poll::Ready(Ok(Some(stream))) => stream,
+            Poll::Ready(Ok(None)) => panic!("expected a stream, got end of connection"),
+            Poll::Ready(Err(_)) => panic!("expected poll_accept_bi to succeed"),
+            Poll::Pending => panic!("expected poll_accept_bi to finish synchronously"),
+        };
+
+        match accepted.poll_data(&mut cx) {
+            Poll::Ready(Ok(Some(mut buf))) => {
+                let mut got = Vec::new();
+                got.extend_from_slice(buf.chunk());
+                buf.advance(buf.remaining());
+                assert_eq!(got, b"hello");
+            }
+            Poll::Ready(Ok(None)) => panic!("expected the leftover payload, got end of stream"),
+            Poll::Ready(Err(_)) => panic!("expected poll_data to succeed"),
+            Poll::Pending => panic!("expected poll_data to finish synchronously"),
+        }
+    }
+
+    /// A `Buf` split across more than one contiguous segment, so a test can prove
+    /// `poll_read_header` doesn't assume `chunk()` exposes an entire chunk's bytes at once.
+    struct MultiSegmentBuf(std::collections::VecDeque<Bytes>);
+
+    impl Buf for MultiSegmentBuf {
+        fn remaining(&self) -> usize {
+            self.0.iter().map(Bytes::len).sum()
+        }
+
+        fn chunk(&self) -> &[u8] {
+            self.0.front().map_or(&[], |segment| segment.as_ref())
+        }
+
+        fn advance(&mut self, mut cnt: usize) {
+            while cnt > 0 {
+                let front = self.0.front_mut().expect("advance past the end of the buffer");
+                if cnt < front.len() {
+                    front.advance(cnt);
+                    cnt = 0;
+                } else {
+                    cnt -= front.len();
+                    self.0.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Yields a single [`MultiSegmentBuf`], then reports end of stream.
+    struct OneShotMultiSegmentStream(Option<MultiSegmentBuf>);
+
+    impl RecvStream for OneShotMultiSegmentStream {
+        type Buf = MultiSegmentBuf;
+        type Error = MockStreamError;
+
+        fn poll_data(
+            &mut self,
+            _cx: &mut task::Context<'_>,
+        ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+            Poll::Ready(Ok(self.0.take()))
+        }
+
+        fn stop_sending(&mut self, _error_code: u64) {}
+
+        fn recv_id(&self) -> StreamId {
+            StreamId::try_from(0u64).unwrap()
+        }
+    }
+
+    #[test]
+    fn poll_read_header_buffers_a_multi_segment_chunk_in_full() {
+        let waker = noop_waker();
+        let mut cx = task::Context::from_waker(&waker);
+
+        let id = SessionId::new(StreamId::try_from(12u64).unwrap());
+        let mut header_bytes = Vec::new();
+        let mut header = stream_header(WEBTRANSPORT_STREAM, id);
+        header_bytes.extend_from_slice(header.chunk());
+        header.advance(header.remaining());
+
+        // Split the header itself across two segments, so a naive `chunk()`-only read would
+        // only ever see the first half.
+        let mid = header_bytes.len() / 2;
+        let mut segments = std::collections::VecDeque::new();
+        segments.push_back(Bytes::copy_from_slice(&header_bytes[..mid]));
+        segments.push_back(Bytes::copy_from_slice(&header_bytes[mid..]));
+
+        let mut stream = OneShotMultiSegmentStream(Some(MultiSegmentBuf(segments)));
+        let mut buf = Vec::new();
+
+        let (signal, session, consumed) = match poll_read_header(&mut stream, &mut buf, &mut cx) {
+            Poll::Ready(Ok(parsed)) => parsed,
+            Poll::Ready(Err(_)) => panic!("expected poll_read_header to succeed"),
+            Poll::Pending => panic!("expected poll_read_header to finish synchronously"),
+        };
+
+        assert_eq!(signal, WEBTRANSPORT_STREAM);
+        assert_eq!(session, id.raw());
+        assert_eq!(consumed, header_bytes.len());
+    }
+}
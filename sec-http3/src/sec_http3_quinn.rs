@@ -0,0 +1,471 @@
+//! Adapter wiring [`quinn`]'s tokio + readiness-based QUIC implementation onto [`crate::quic`]'s
+//! traits, and backing [`crate::runtime::QuinnRuntime`].
+//!
+//! Every `poll_*` method here constructs a fresh quinn future and polls it once rather than
+//! storing it across calls: the state that matters (queued incoming streams/datagrams, stream
+//! send capacity) lives in the cheaply-cloned `quinn::Connection` handle itself, not in any one
+//! future, so re-issuing the call on every poll loses nothing and re-registers the waker with
+//! the latest `Context`.
+
+pub use ::quinn;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{self, Poll};
+
+use bytes::{Buf, Bytes};
+
+use crate::error::Code;
+use crate::quic::{self, Error as _, StreamId};
+
+/// A bound QUIC socket, as produced by [`quinn::Endpoint::server`]/[`quinn::Endpoint::client`].
+pub type Endpoint = quinn::Endpoint;
+
+/// Tracks whether [`Connection`] was constructed from a completed handshake or from 0-RTT
+/// (early) data, and if the latter, whether that early data has been confirmed accepted yet.
+enum ZeroRtt {
+    /// Constructed after a full handshake; there was never anything to accept.
+    NotAttempted,
+    /// Constructed via [`Connection::new_0rtt`]; still waiting on the peer's confirmation.
+    Pending(quinn::ZeroRttAccepted),
+    /// The handshake has confirmed, one way or the other.
+    Resolved(bool),
+}
+
+/// A QUIC connection backed by quinn's Arc-handle, readiness-based driver.
+pub struct Connection {
+    conn: quinn::Connection,
+    zero_rtt: ZeroRtt,
+}
+
+impl Connection {
+    /// Wrap a connection obtained from a completed handshake.
+    pub fn new(conn: quinn::Connection) -> Self {
+        Self {
+            conn,
+            zero_rtt: ZeroRtt::NotAttempted,
+        }
+    }
+
+    /// Wrap a connection obtained early via [`quinn::Connecting::into_0rtt`], before the
+    /// handshake has confirmed whether the peer accepted the early data it was used to send.
+    pub fn new_0rtt(conn: quinn::Connection, zero_rtt_accepted: quinn::ZeroRttAccepted) -> Self {
+        Self {
+            conn,
+            zero_rtt: ZeroRtt::Pending(zero_rtt_accepted),
+        }
+    }
+}
+
+/// Wraps [`quinn::ConnectionError`] to satisfy [`quic::Error`] (a trait foreign to this crate)
+/// for a type foreign to this crate, per Rust's orphan rules.
+#[derive(Debug)]
+pub struct ConnectionError(pub quinn::ConnectionError);
+
+impl std::fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ConnectionError {}
+
+impl quic::Error for ConnectionError {
+    fn is_timeout(&self) -> bool {
+        matches!(self.0, quinn::ConnectionError::TimedOut)
+    }
+
+    fn err_code(&self) -> Option<u64> {
+        match self.0 {
+            quinn::ConnectionError::ApplicationClosed(ref close) => Some(close.error_code.into()),
+            _ => None,
+        }
+    }
+}
+
+impl From<quinn::ConnectionError> for ConnectionError {
+    fn from(e: quinn::ConnectionError) -> Self {
+        Self(e)
+    }
+}
+
+/// Wraps [`quinn::WriteError`]; see [`ConnectionError`] for why a newtype is needed here.
+#[derive(Debug)]
+pub struct WriteError(pub quinn::WriteError);
+
+impl std::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+impl quic::Error for WriteError {
+    fn is_timeout(&self) -> bool {
+        matches!(
+            self.0,
+            quinn::WriteError::ConnectionLost(quinn::ConnectionError::TimedOut)
+        )
+    }
+
+    fn err_code(&self) -> Option<u64> {
+        match self.0 {
+            quinn::WriteError::Stopped(code) => Some(code.into()),
+            _ => None,
+        }
+    }
+}
+
+impl From<quinn::WriteError> for WriteError {
+    fn from(e: quinn::WriteError) -> Self {
+        Self(e)
+    }
+}
+
+/// Wraps [`quinn::ReadError`]; see [`ConnectionError`] for why a newtype is needed here.
+#[derive(Debug)]
+pub struct ReadError(pub quinn::ReadError);
+
+impl std::fmt::Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl quic::Error for ReadError {
+    fn is_timeout(&self) -> bool {
+        matches!(
+            self.0,
+            quinn::ReadError::ConnectionLost(quinn::ConnectionError::TimedOut)
+        )
+    }
+
+    fn err_code(&self) -> Option<u64> {
+        match self.0 {
+            quinn::ReadError::Reset(code) => Some(code.into()),
+            _ => None,
+        }
+    }
+}
+
+impl From<quinn::ReadError> for ReadError {
+    fn from(e: quinn::ReadError) -> Self {
+        Self(e)
+    }
+}
+
+fn stream_id(id: quinn::StreamId) -> StreamId {
+    StreamId::try_from(u64::from(id)).expect("quinn never allocates a stream id h3 can't hold")
+}
+
+/// The sending half of a stream, or a unidirectional send-only stream.
+pub struct SendStream {
+    stream: quinn::SendStream,
+    finished: bool,
+}
+
+impl quic::SendStream for SendStream {
+    type Error = WriteError;
+
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, Self::Error>> {
+        let mut write = self.stream.write(buf.chunk());
+        match Pin::new(&mut write).poll(cx) {
+            Poll::Ready(Ok(n)) => {
+                buf.advance(n);
+                Poll::Ready(Ok(n))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_finish(&mut self, _cx: &mut task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if !self.finished {
+            self.finished = true;
+            return Poll::Ready(self.stream.finish().map_err(WriteError::from));
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn reset(&mut self, reset_code: u64) {
+        let _ = self
+            .stream
+            .reset(quinn::VarInt::from_u64(reset_code).unwrap_or(quinn::VarInt::MAX));
+    }
+
+    fn send_id(&self) -> StreamId {
+        stream_id(self.stream.id())
+    }
+}
+
+/// The receiving half of a stream, or a unidirectional receive-only stream.
+pub struct RecvStream(quinn::RecvStream);
+
+impl quic::RecvStream for RecvStream {
+    type Buf = Bytes;
+    type Error = ReadError;
+
+    fn poll_data(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+        let mut read = self.0.read_chunk(usize::MAX, true);
+        match Pin::new(&mut read).poll(cx) {
+            Poll::Ready(Ok(Some(chunk))) => Poll::Ready(Ok(Some(chunk.bytes))),
+            Poll::Ready(Ok(None)) => Poll::Ready(Ok(None)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn stop_sending(&mut self, error_code: u64) {
+        let _ = self
+            .0
+            .stop(quinn::VarInt::from_u64(error_code).unwrap_or(quinn::VarInt::MAX));
+    }
+
+    fn recv_id(&self) -> StreamId {
+        stream_id(self.0.id())
+    }
+}
+
+/// A bidirectional stream, delegating each half to [`SendStream`]/[`RecvStream`].
+pub struct BidiStream {
+    send: SendStream,
+    recv: RecvStream,
+}
+
+impl quic::SendStream for BidiStream {
+    type Error = WriteError;
+
+    fn poll_send<D: Buf>(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buf: &mut D,
+    ) -> Poll<Result<usize, Self::Error>> {
+        self.send.poll_send(cx, buf)
+    }
+
+    fn poll_finish(&mut self, cx: &mut task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.send.poll_finish(cx)
+    }
+
+    fn reset(&mut self, reset_code: u64) {
+        self.send.reset(reset_code)
+    }
+
+    fn send_id(&self) -> StreamId {
+        self.send.send_id()
+    }
+}
+
+impl quic::RecvStream for BidiStream {
+    type Buf = Bytes;
+    type Error = ReadError;
+
+    fn poll_data(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Self::Buf>, Self::Error>> {
+        self.recv.poll_data(cx)
+    }
+
+    fn stop_sending(&mut self, error_code: u64) {
+        self.recv.stop_sending(error_code)
+    }
+
+    fn recv_id(&self) -> StreamId {
+        self.recv.recv_id()
+    }
+}
+
+fn close_code(code: Code) -> quinn::VarInt {
+    quinn::VarInt::from_u64(code.value()).unwrap_or(quinn::VarInt::MAX)
+}
+
+/// A producer of outgoing streams, cheaply cloned from the owning [`Connection`]'s handle.
+#[derive(Clone)]
+pub struct OpenStreams {
+    conn: quinn::Connection,
+}
+
+impl quic::OpenStreams for OpenStreams {
+    type BidiStream = BidiStream;
+    type SendStream = SendStream;
+    type RecvStream = RecvStream;
+    type OpenError = ConnectionError;
+
+    fn poll_open_bidi(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Self::BidiStream, Self::OpenError>> {
+        let mut open = self.conn.open_bi();
+        match Pin::new(&mut open).poll(cx) {
+            Poll::Ready(Ok((send, recv))) => Poll::Ready(Ok(BidiStream {
+                send: SendStream {
+                    stream: send,
+                    finished: false,
+                },
+                recv: RecvStream(recv),
+            })),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_open_uni(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Self::SendStream, Self::OpenError>> {
+        let mut open = self.conn.open_uni();
+        match Pin::new(&mut open).poll(cx) {
+            Poll::Ready(Ok(send)) => Poll::Ready(Ok(SendStream {
+                stream: send,
+                finished: false,
+            })),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn close(&mut self, code: Code, reason: &[u8]) {
+        self.conn.close(close_code(code), reason);
+    }
+}
+
+impl quic::Connection for Connection {
+    type BidiStream = BidiStream;
+    type SendStream = SendStream;
+    type RecvStream = RecvStream;
+    type OpenStreams = OpenStreams;
+    type AcceptError = ConnectionError;
+    type OpenError = ConnectionError;
+
+    fn poll_accept_recv(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Self::RecvStream>, Self::AcceptError>> {
+        let mut accept = self.conn.accept_uni();
+        match Pin::new(&mut accept).poll(cx) {
+            Poll::Ready(Ok(recv)) => Poll::Ready(Ok(Some(RecvStream(recv)))),
+            Poll::Ready(Err(
+                quinn::ConnectionError::LocallyClosed | quinn::ConnectionError::ApplicationClosed(_),
+            )) => Poll::Ready(Ok(None)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_accept_bidi(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Self::BidiStream>, Self::AcceptError>> {
+        let mut accept = self.conn.accept_bi();
+        match Pin::new(&mut accept).poll(cx) {
+            Poll::Ready(Ok((send, recv))) => Poll::Ready(Ok(Some(BidiStream {
+                send: SendStream {
+                    stream: send,
+                    finished: false,
+                },
+                recv: RecvStream(recv),
+            }))),
+            Poll::Ready(Err(
+                quinn::ConnectionError::LocallyClosed | quinn::ConnectionError::ApplicationClosed(_),
+            )) => Poll::Ready(Ok(None)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_open_bidi(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Self::BidiStream, Self::OpenError>> {
+        self.opener().poll_open_bidi(cx)
+    }
+
+    fn poll_open_send(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Self::SendStream, Self::OpenError>> {
+        self.opener().poll_open_uni(cx)
+    }
+
+    fn opener(&self) -> Self::OpenStreams {
+        OpenStreams {
+            conn: self.conn.clone(),
+        }
+    }
+
+    fn close(&mut self, code: Code, reason: &[u8]) {
+        self.conn.close(close_code(code), reason);
+    }
+
+    fn poll_zero_rtt_accepted(&mut self, cx: &mut task::Context<'_>) -> Poll<bool> {
+        match &mut self.zero_rtt {
+            ZeroRtt::NotAttempted => Poll::Ready(false),
+            ZeroRtt::Resolved(accepted) => Poll::Ready(*accepted),
+            ZeroRtt::Pending(accepted) => match Pin::new(accepted).poll(cx) {
+                Poll::Ready(accepted) => {
+                    self.zero_rtt = ZeroRtt::Resolved(accepted);
+                    Poll::Ready(accepted)
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+
+    fn poll_accept_datagram(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> Poll<Result<Option<Bytes>, Self::AcceptError>> {
+        let mut read = self.conn.read_datagram();
+        match Pin::new(&mut read).poll(cx) {
+            Poll::Ready(Ok(data)) => Poll::Ready(Ok(Some(data))),
+            Poll::Ready(Err(
+                quinn::ConnectionError::LocallyClosed | quinn::ConnectionError::ApplicationClosed(_),
+            )) => Poll::Ready(Ok(None)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn send_datagram(&mut self, data: Bytes) -> Result<(), quic::SendDatagramError> {
+        self.conn.send_datagram(data).map_err(|e| match e {
+            quinn::SendDatagramError::UnsupportedByPeer => {
+                quic::SendDatagramError::UnsupportedByPeer
+            }
+            quinn::SendDatagramError::Disabled => quic::SendDatagramError::Disabled,
+            quinn::SendDatagramError::TooLarge => quic::SendDatagramError::TooLarge,
+            quinn::SendDatagramError::ConnectionLost(e) => {
+                quic::SendDatagramError::ConnectionLost(Box::new(ConnectionError::from(e)))
+            }
+        })
+    }
+
+    fn max_datagram_size(&self) -> Option<usize> {
+        self.conn.max_datagram_size()
+    }
+
+    fn stats(&self) -> quic::ConnectionStats {
+        let stats = self.conn.stats();
+        quic::ConnectionStats {
+            smoothed_rtt: stats.path.rtt,
+            // quinn's stats only expose the smoothed estimate, so this is left at zero.
+            latest_rtt: std::time::Duration::ZERO,
+            congestion_window: stats.path.cwnd,
+            bytes_in_flight: 0,
+            packets_sent: stats.path.sent_packets,
+            packets_lost: stats.path.lost_packets,
+            path_mtu: stats.path.current_mtu,
+            max_udp_payload_size: stats.path.current_mtu as u64,
+        }
+    }
+}